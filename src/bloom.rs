@@ -0,0 +1,138 @@
+//! Bloom-filter set reconciliation for syncing two DAG replicas without exchanging
+//! their full history. Membership testing derives its k bit positions directly from
+//! k disjoint 4-byte windows of a [`NodeId`] (it's already a hash), rather than
+//! hashing it again.
+//!
+//! Protocol: peer A sends [`Bloom::to_bytes`] (plus `m`/`k`) to B; B calls
+//! [`missing_against`] to get every node A is guaranteed not to have, and ships
+//! those (walking up to a node's parent too if it doesn't resolve locally, since a
+//! Bloom filter never guarantees presence).
+//!
+//! TODO: `Document::bloom`/`Document::missing_against` aren't wired up yet; this
+//! operates on a bare [`NodeStore`] only.
+
+use crate::node::{Node, NodeId};
+use crate::node_store::NodeStore;
+use std::convert::TryInto;
+
+/// A Bloom filter over [`NodeId`]s, sized in bits (`m`) with `k` probes per id.
+#[derive(Debug, Clone)]
+pub struct Bloom {
+    bits: Vec<u8>,
+    m: usize,
+    k: usize,
+}
+
+impl Bloom {
+    /// `m` is the filter size in bits; `k` is the number of 4-byte windows of the id
+    /// (and hence the number of bits set per insertion). `k` must be at most 8, since a
+    /// 32-byte `NodeId` only has 8 disjoint 4-byte windows.
+    pub fn new(m: usize, k: usize) -> Self {
+        assert!(k >= 1 && k <= 8, "k must be between 1 and 8");
+        assert!(m >= 1, "m must be at least 1 bit");
+        Bloom {
+            bits: vec![0u8; (m + 7) / 8],
+            m,
+            k,
+        }
+    }
+
+    fn positions(&self, id: &NodeId) -> impl Iterator<Item = usize> + '_ {
+        (0..self.k).map(move |i| {
+            let window: [u8; 4] = id.0[i * 4..i * 4 + 4].try_into().unwrap();
+            (u32::from_be_bytes(window) as usize) % self.m
+        })
+    }
+
+    pub fn insert(&mut self, id: &NodeId) {
+        for pos in self.positions(id).collect::<Vec<_>>() {
+            self.bits[pos / 8] |= 1 << (pos % 8);
+        }
+    }
+
+    /// `false` means `id` is guaranteed absent; `true` means it's probably present
+    /// (subject to false positives).
+    pub fn contains(&self, id: &NodeId) -> bool {
+        self.positions(id)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .all(|pos| self.bits[pos / 8] & (1 << (pos % 8)) != 0)
+    }
+
+    pub fn m(&self) -> usize {
+        self.m
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bits.clone()
+    }
+
+    pub fn from_bytes(bytes: &[u8], m: usize, k: usize) -> Self {
+        let mut bloom = Bloom::new(m, k);
+        let n = bloom.bits.len().min(bytes.len());
+        bloom.bits[..n].copy_from_slice(&bytes[..n]);
+        bloom
+    }
+}
+
+/// Builds a Bloom filter over every [`NodeId`] held by `store`.
+pub fn build(store: &dyn NodeStore, m: usize, k: usize) -> Bloom {
+    let mut bloom = Bloom::new(m, k);
+    for id in store.ids() {
+        bloom.insert(&id);
+    }
+    bloom
+}
+
+/// Every node in `store` that `their_bloom` proves the sender doesn't have.
+pub fn missing_against(store: &dyn NodeStore, their_bloom: &Bloom) -> Vec<Node> {
+    store
+        .ids()
+        .into_iter()
+        .filter(|id| !their_bloom.contains(id))
+        .filter_map(|id| store.get(&id))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::node_store::MemoryNodeStore;
+
+    #[test]
+    fn guaranteed_absent_ids_are_reported_missing() {
+        let mut store = MemoryNodeStore::new();
+        let root_id = store.put(Node::root(0));
+
+        let their_bloom = Bloom::new(2048, 4);
+        let missing = missing_against(&store, &their_bloom);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].node_id(), root_id);
+    }
+
+    #[test]
+    fn present_ids_are_not_reported_missing() {
+        let mut store = MemoryNodeStore::new();
+        let root_id = store.put(Node::root(0));
+
+        let mut their_bloom = Bloom::new(2048, 4);
+        their_bloom.insert(&root_id);
+
+        let missing = missing_against(&store, &their_bloom);
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn roundtrips_through_bytes() {
+        let mut bloom = Bloom::new(64, 3);
+        let id = NodeId([7; 32]);
+        bloom.insert(&id);
+
+        let restored = Bloom::from_bytes(&bloom.to_bytes(), 64, 3);
+        assert!(restored.contains(&id));
+    }
+}
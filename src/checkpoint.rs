@@ -0,0 +1,163 @@
+//! A checkpoint cache for materialized documents: instead of re-walking a node's
+//! entire ancestor chain from the `Null` root every time it's projected, periodically
+//! cache the materialized buffer and replay forward only from the nearest checkpoint.
+//!
+//! TODO: `Document::checkpoint(node_id)` is meant to consult this cache
+//! transparently; for now callers have to call [`project`] directly.
+
+use crate::node::{Node, NodeId};
+use crate::node_store::NodeStore;
+use crate::util::mutstr::MutStr;
+use std::collections::{HashMap, VecDeque};
+
+/// An LRU-bounded cache of materialized buffers keyed by the [`NodeId`] they were
+/// produced at.
+pub struct CheckpointCache {
+    buffers: HashMap<NodeId, MutStr>,
+    /// Most-recently-used id at the back; eviction pops from the front.
+    recency: VecDeque<NodeId>,
+    capacity: usize,
+}
+
+impl CheckpointCache {
+    pub fn new(capacity: usize) -> Self {
+        CheckpointCache {
+            buffers: HashMap::new(),
+            recency: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn get(&mut self, id: &NodeId) -> Option<MutStr> {
+        if !self.buffers.contains_key(id) {
+            return None;
+        }
+        self.touch(id);
+        self.buffers.get(id).cloned()
+    }
+
+    pub fn insert(&mut self, id: NodeId, buf: MutStr) {
+        if self.buffers.insert(id.clone(), buf).is_none() {
+            self.recency.push_back(id.clone());
+        }
+        self.touch(&id);
+        while self.buffers.len() > self.capacity {
+            if let Some(evict) = self.recency.pop_front() {
+                self.buffers.remove(&evict);
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    fn touch(&mut self, id: &NodeId) {
+        if let Some(pos) = self.recency.iter().position(|i| i == id) {
+            self.recency.remove(pos);
+        }
+        self.recency.push_back(id.clone());
+    }
+}
+
+/// Materializes `target` by walking its parent chain backwards until it finds a cached
+/// checkpoint (or the root), then replaying the remaining suffix forward into a clone of
+/// that checkpoint's buffer. Every `checkpoint_interval`-th node visited while walking
+/// down from an uncached target is stored back into `cache` for future reuse.
+///
+/// # Panics
+///
+/// Panics if `checkpoint_interval` is `0` (there's no sensible "every 0th node").
+pub fn project(
+    store: &dyn NodeStore,
+    cache: &mut CheckpointCache,
+    target: &NodeId,
+    checkpoint_interval: usize,
+) -> MutStr {
+    assert!(checkpoint_interval > 0, "checkpoint_interval must be at least 1");
+
+    let mut suffix: Vec<Node> = Vec::new();
+    let mut cursor = target.clone();
+
+    let mut buf = loop {
+        if let Some(cached) = cache.get(&cursor) {
+            break cached;
+        }
+        let node = store.get(&cursor).expect("dangling NodeId in project()");
+        let parent = node.parent().cloned();
+        suffix.push(node);
+        match parent {
+            Some(p) => cursor = p,
+            None => break MutStr::new(),
+        }
+    };
+
+    for (i, node) in suffix.iter().rev().enumerate() {
+        node.project(&mut buf, None);
+        if i % checkpoint_interval == 0 {
+            cache.insert(node.node_id(), buf.clone());
+        }
+    }
+
+    cache.insert(target.clone(), buf.clone());
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::node::Action;
+    use crate::node_store::MemoryNodeStore;
+
+    fn insert(store: &mut MemoryNodeStore, tick: u32, parent: &NodeId, body: &str) -> NodeId {
+        let node = Node::new(
+            tick,
+            Some(parent.clone()),
+            Action::Insert {
+                offset: 0,
+                body: body.to_string(),
+            },
+        );
+        store.put(node)
+    }
+
+    #[test]
+    fn replays_only_suffix_after_cached_checkpoint() {
+        let mut store = MemoryNodeStore::new();
+        let root_id = store.put(Node::root(0));
+        let a = insert(&mut store, 1, &root_id, "c");
+        let b = insert(&mut store, 2, &a, "b");
+        let c = insert(&mut store, 3, &b, "a");
+
+        let mut cache = CheckpointCache::new(8);
+        let buf_a = project(&store, &mut cache, &a, 1);
+        assert_eq!(buf_a.to_string(), "c");
+
+        // `a` is now a checkpoint; projecting `c` only has to replay b and c.
+        let buf_c = project(&store, &mut cache, &c, 1);
+        assert_eq!(buf_c.to_string(), "abc");
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint_interval must be at least 1")]
+    fn rejects_zero_checkpoint_interval() {
+        let mut store = MemoryNodeStore::new();
+        let root_id = store.put(Node::root(0));
+        let mut cache = CheckpointCache::new(8);
+        project(&store, &mut cache, &root_id, 0);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = CheckpointCache::new(2);
+        cache.insert(NodeId([1; 32]), MutStr::new());
+        cache.insert(NodeId([2; 32]), MutStr::new());
+        cache.insert(NodeId([3; 32]), MutStr::new());
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&NodeId([1; 32])).is_none());
+        assert!(cache.get(&NodeId([3; 32])).is_some());
+    }
+}
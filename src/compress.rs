@@ -0,0 +1,185 @@
+//! Yaz0-style LZ77 compression, the scheme decomp-toolkit added via orthrus-ncompress.
+//! Used to shrink large stored [`crate::node::Action::Insert`] bodies and materialized
+//! checkpoint buffers before they hit a [`crate::node_store::NodeStore`].
+//!
+//! Format: a 16-byte header — ASCII magic `Yaz0`, a big-endian `u32` of the uncompressed
+//! length, then 8 reserved zero bytes — followed by the compressed stream.
+//!
+//! The stream is a sequence of groups, each introduced by one "code" byte whose 8 bits
+//! are read MSB-first: a `1` bit means "emit the next literal byte"; a `0` bit means a
+//! back-reference encoded as two bytes `b1, b2`, where `distance = ((b1 & 0x0F) << 8 |
+//! b2) + 1` and `count = b1 >> 4`, except that `count == 0` means "read a third byte and
+//! set `count = third + 0x12`" (otherwise `count += 2`). The decoder then copies `count`
+//! bytes from `output_len - distance`, one byte at a time so overlapping copies (distance
+//! shorter than count) replicate correctly.
+
+use std::convert::TryInto;
+
+const MAGIC: &[u8; 4] = b"Yaz0";
+const HEADER_LEN: usize = 16;
+// A back-reference's nibble count field reserves 0 to mean "read an extended count
+// byte", so the smallest count expressible in the short form is 2 + 1 = 3.
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 0xFF + 0x12;
+const MAX_DISTANCE: usize = 0x1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecompressError {
+    TooShort,
+    BadMagic,
+    Truncated,
+}
+
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN + input.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(input.len() as u32).to_be_bytes());
+    out.extend_from_slice(&[0u8; 8]);
+
+    let mut pos = 0;
+    while pos < input.len() {
+        let mut code = 0u8;
+        let group_start = out.len();
+        out.push(0); // placeholder code byte, filled in below
+        let mut group = Vec::new();
+
+        for bit in 0..8 {
+            if pos >= input.len() {
+                break;
+            }
+            match best_match(input, pos) {
+                Some((distance, count)) => {
+                    let b1 = if count >= 0x12 {
+                        0
+                    } else {
+                        ((count - 2) << 4) as u8
+                    };
+                    let b1 = b1 | (((distance - 1) >> 8) as u8 & 0x0F);
+                    let b2 = ((distance - 1) & 0xFF) as u8;
+                    group.push(b1);
+                    group.push(b2);
+                    if count >= 0x12 {
+                        group.push((count - 0x12) as u8);
+                    }
+                    pos += count;
+                }
+                None => {
+                    code |= 1 << (7 - bit);
+                    group.push(input[pos]);
+                    pos += 1;
+                }
+            }
+        }
+
+        out[group_start] = code;
+        out.extend_from_slice(&group);
+    }
+
+    out
+}
+
+fn best_match(input: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let window_start = pos.saturating_sub(MAX_DISTANCE);
+    let mut best: Option<(usize, usize)> = None;
+
+    for start in window_start..pos {
+        let max_len = (input.len() - pos).min(MAX_MATCH);
+        let mut len = 0;
+        while len < max_len && input[start + (len % (pos - start))] == input[pos + len] {
+            len += 1;
+        }
+        if len >= MIN_MATCH {
+            let distance = pos - start;
+            if best.map_or(true, |(_, best_len)| len > best_len) {
+                best = Some((distance, len));
+            }
+        }
+    }
+
+    best
+}
+
+pub fn decompress(input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    if input.len() < HEADER_LEN {
+        return Err(DecompressError::TooShort);
+    }
+    if &input[0..4] != MAGIC {
+        return Err(DecompressError::BadMagic);
+    }
+    let uncompressed_len =
+        u32::from_be_bytes(input[4..8].try_into().unwrap()) as usize;
+
+    let mut out = Vec::with_capacity(uncompressed_len);
+    let mut pos = HEADER_LEN;
+
+    while out.len() < uncompressed_len {
+        let code = *input.get(pos).ok_or(DecompressError::Truncated)?;
+        pos += 1;
+
+        for bit in 0..8 {
+            if out.len() >= uncompressed_len {
+                break;
+            }
+            let literal = code & (1 << (7 - bit)) != 0;
+            if literal {
+                let byte = *input.get(pos).ok_or(DecompressError::Truncated)?;
+                pos += 1;
+                out.push(byte);
+            } else {
+                let b1 = *input.get(pos).ok_or(DecompressError::Truncated)?;
+                let b2 = *input.get(pos + 1).ok_or(DecompressError::Truncated)?;
+                pos += 2;
+                let distance = (((b1 & 0x0F) as usize) << 8 | b2 as usize) + 1;
+                let mut count = (b1 >> 4) as usize;
+                if count == 0 {
+                    let third = *input.get(pos).ok_or(DecompressError::Truncated)?;
+                    pos += 1;
+                    count = third as usize + 0x12;
+                } else {
+                    count += 2;
+                }
+
+                let mut src = out.len().checked_sub(distance).ok_or(DecompressError::Truncated)?;
+                for _ in 0..count {
+                    let byte = out[src];
+                    out.push(byte);
+                    src += 1;
+                }
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn roundtrips_empty_input() {
+        let compressed = compress(&[]);
+        assert_eq!(decompress(&compressed).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn roundtrips_short_literal_input() {
+        let input = b"hello";
+        let compressed = compress(input);
+        assert_eq!(decompress(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn roundtrips_repetitive_input() {
+        let input = "abababababababababababab".repeat(10);
+        let compressed = compress(input.as_bytes());
+        assert!(compressed.len() < input.len());
+        assert_eq!(decompress(&compressed).unwrap(), input.as_bytes());
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let err = decompress(&[0u8; 16]).unwrap_err();
+        assert_eq!(err, DecompressError::BadMagic);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{cursor::Cursor, document::Document, util::mutstr::MutStr};
 use serde::Serialize;
 use sha2::{Digest, Sha512Trunc256};
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
 use tracing::trace;
 
 #[derive(Debug, Serialize, Clone)]
@@ -10,13 +13,57 @@ pub enum Action {
         offset: usize,
         body: String,
     },
-    #[allow(unused)]
     Delete {
         offset: usize,
+        len: usize,
+        /// The `Insert` this deletion's chars came from, when the selection lies
+        /// entirely within one insert. Lets sibling branches tombstone and union
+        /// ranges of the same immutable insert instead of replaying positionally.
+        source: Option<NodeId>,
+        /// `offset` translated into a char index into `source`'s own body. Set iff
+        /// `source` is. This is the space [`project_with_tombstones`] unions in;
+        /// `offset` alone is only a live buffer position, and two sibling deletes
+        /// agree on it only by coincidence once `source` sits past buffer offset 0.
+        source_offset: Option<usize>,
     },
 }
 
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+/// Discriminant bytes used by [`Node::encode`] / [`Node::decode`]. These are part of the
+/// on-disk/wire format, so they must never be reassigned once shipped.
+const ACTION_NULL: u8 = 0;
+const ACTION_INSERT: u8 = 1;
+const ACTION_DELETE: u8 = 2;
+
+/// Error returned by [`Node::decode`] when a byte stream produced by [`Node::encode`]
+/// is truncated or otherwise malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    UnexpectedEof,
+    UnknownAction(u8),
+    InvalidUtf8,
+    /// A storage-layer wrapper byte (see [`crate::node_store`]) didn't match any
+    /// known framing.
+    BadStorageTag(u8),
+    /// A storage-layer wrapper claimed its payload was Yaz0-compressed, but
+    /// decompression failed.
+    Decompress,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "unexpected end of input"),
+            DecodeError::UnknownAction(tag) => write!(f, "unknown action discriminant {}", tag),
+            DecodeError::InvalidUtf8 => write!(f, "insert body is not valid utf-8"),
+            DecodeError::BadStorageTag(tag) => write!(f, "unknown storage framing tag {}", tag),
+            DecodeError::Decompress => write!(f, "failed to decompress stored payload"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[derive(Debug, Serialize, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct NodeId(pub [u8; 32]);
 
 #[derive(Debug, Clone)]
@@ -26,14 +73,122 @@ pub struct Node {
     pub action: Action,
 }
 
+/// Alphabets for [`NodeId::to_base`], in the style of rustc_data_structures'
+/// `base_n` encoder: a generic big-integer-to-string conversion parameterized by
+/// radix, not a standards-compliant codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Base32,
+    Base58,
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+impl Radix {
+    fn alphabet(self) -> &'static [u8] {
+        match self {
+            Radix::Base32 => BASE32_ALPHABET,
+            Radix::Base58 => BASE58_ALPHABET,
+        }
+    }
+}
+
+/// Encodes `bytes`, read as one big-endian integer, in the given alphabet. A run of
+/// leading zero bytes is rendered as that many copies of the alphabet's zero digit
+/// (the same convention base58 uses for leading zero bytes).
+fn encode_base(bytes: &[u8], alphabet: &[u8]) -> String {
+    let radix = alphabet.len() as u32;
+    let mut work = bytes.to_vec();
+    let mut digits = Vec::new();
+
+    while work.iter().any(|&b| b != 0) {
+        let mut rem: u32 = 0;
+        for byte in work.iter_mut() {
+            let cur = (rem << 8) | (*byte as u32);
+            *byte = (cur / radix) as u8;
+            rem = cur % radix;
+        }
+        digits.push(alphabet[rem as usize]);
+    }
+
+    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut out: Vec<u8> = std::iter::repeat(alphabet[0]).take(leading_zeros).collect();
+    out.extend(digits.iter().rev());
+    if out.is_empty() {
+        out.push(alphabet[0]);
+    }
+    String::from_utf8(out).unwrap()
+}
+
 impl NodeId {
-    pub fn hex4(&self) -> String {
-        hex::encode(&self.0[0..2])
+    /// Renders the full 32-byte id in the given base — readable in logs and any
+    /// textual wire format, and collision-free across a much larger DAG than a fixed
+    /// hex prefix would be.
+    pub fn to_base(&self, radix: Radix) -> String {
+        encode_base(&self.0, radix.alphabet())
+    }
+
+    /// The shortest prefix of `self.to_base(radix)` that's unique among `others`,
+    /// in the style of git's abbreviated hashes. Always at least one character, and
+    /// the full string if no prefix length distinguishes `self` from every entry in
+    /// `others`.
+    pub fn short_prefix(&self, others: &[NodeId], radix: Radix) -> String {
+        let full = self.to_base(radix);
+        let other_encodings: Vec<String> = others
+            .iter()
+            .filter(|id| *id != self)
+            .map(|id| id.to_base(radix))
+            .collect();
+
+        for len in 1..=full.len() {
+            let candidate = &full[..len];
+            if !other_encodings.iter().any(|other| other.starts_with(candidate)) {
+                return candidate.to_string();
+            }
+        }
+        full
+    }
+
+    /// A short base58 prefix for trace output: the single rendering path [`Node::diag`],
+    /// [`Node::project`], and friends all use to print a `NodeId`. 6 base58 chars cover
+    /// ~34 bits, which stays comfortably collision-free for the DAG sizes trace logs
+    /// are read at by a human — well past what a fixed hex prefix could offer.
+    fn trace_id(&self) -> String {
+        self.to_base(Radix::Base58).chars().take(6).collect()
     }
 }
 
 static NULL: &'static [u8; 32] = &[0; 32];
 
+/// Translates a live buffer offset into a char index relative to `source`'s own
+/// body — the space `project_with_tombstones` unions tombstones in, as opposed to
+/// the live, shifting buffer position `offset` otherwise means. Split out of
+/// `Node::delete` so it's testable without a live `Cursor`.
+fn translate_to_source_offset(buffer_offset: usize, source: &Node) -> usize {
+    buffer_offset - source.offset()
+}
+
+/// Narrows `value` to the big-endian `u32` bytes [`Node::encode`] writes its fields as,
+/// panicking rather than truncating if it doesn't fit. `field` names the offending
+/// field in the panic message.
+fn encode_u32(value: usize, field: &str) -> [u8; 4] {
+    let narrowed: u32 = value
+        .try_into()
+        .unwrap_or_else(|_| panic!("{} {} exceeds u32::MAX and can't be encoded", field, value));
+    narrowed.to_be_bytes()
+}
+
+/// Splits off and returns the first `n` bytes of `*cursor`, advancing it past them.
+fn take<'a>(cursor: &mut &'a [u8], n: usize) -> Result<&'a [u8], DecodeError> {
+    if cursor.len() < n {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let (head, tail) = cursor.split_at(n);
+    *cursor = tail;
+    Ok(head)
+}
+
 impl Node {
     #[allow(unused)]
     pub fn new(tick: u32, parent: Option<NodeId>, action: Action) -> Self {
@@ -62,13 +217,19 @@ impl Node {
             parent: Some(cursor.node_id.clone()),
         }
     }
-    pub fn delete(cursor: &Cursor) -> Self {
+    /// `len` is the selection length (in chars) to delete, and `source` is the
+    /// `Insert` node the deleted chars belong to, when the selection lies entirely
+    /// within one insert.
+    pub fn delete(cursor: &Cursor, len: usize, source: Option<&Node>) -> Self {
         let tick = cursor.doc().increment_clock();
 
         Node {
             tick,
             action: Action::Delete {
                 offset: cursor.offset,
+                len,
+                source: source.map(Node::node_id),
+                source_offset: source.map(|s| translate_to_source_offset(cursor.offset, s)),
             },
             parent: Some(cursor.node_id.clone()),
         }
@@ -76,20 +237,25 @@ impl Node {
     pub fn parent(&self) -> Option<&NodeId> {
         self.parent.as_ref()
     }
-    pub fn parent_hex4(&self) -> String {
-        if let Some(p) = &self.parent {
-            p.hex4()
-        } else {
-            "NA".to_string()
+    fn parent_trace_id(&self) -> String {
+        match &self.parent {
+            Some(p) => p.trace_id(),
+            None => "NA".to_string(),
         }
     }
     pub fn diag(&self) -> String {
         use crate::node::Action::*;
-        match &self.action {
+        let body = match &self.action {
             Null => "NULL".to_string(),
             Action::Insert { offset, body } => format!("{} @ {}", body, offset),
-            Action::Delete { offset } => format!("␡ @ {}", offset),
-        }
+            Action::Delete { offset, len, .. } => format!("␡ {} @ {}", len, offset),
+        };
+        format!(
+            "{}: {} ({})",
+            self.node_id().trace_id(),
+            body,
+            self.parent_trace_id()
+        )
     }
     pub fn offset(&self) -> usize {
         use crate::node::Action::*;
@@ -98,16 +264,110 @@ impl Node {
             Insert { offset, .. } | Delete { offset, .. } => *offset,
         }
     }
-    #[allow(unused)]
+    /// Canonical binary encoding of this node, in the order hashed by [`Node::node_id`]:
+    /// a fixed 32-byte parent id (all-zero when there is none), the tick as big-endian
+    /// `u32`, a single discriminant byte for the action, and then the action's own
+    /// fields. Unlike feeding `serde_json` output into the hasher, this format never
+    /// changes shape under a `Serialize` derive tweak, so it is safe to rely on for
+    /// both hashing and on-disk/wire storage.
+    ///
+    /// `body.len()`, `offset`, and `len` are each written as big-endian `u32`, so this
+    /// panics rather than silently truncating if any of them exceeds `u32::MAX` — a
+    /// `Node` that large isn't one this format can represent, and `node_id()` returns
+    /// a plain `NodeId` rather than a `Result`, so the right place to catch that is here rather
+    /// than by making every caller of `node_id()` handle an encoding error.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self.parent() {
+            Some(parent) => out.extend_from_slice(&parent.0),
+            None => out.extend_from_slice(NULL),
+        }
+        out.extend_from_slice(&self.tick.to_be_bytes());
+        match &self.action {
+            Action::Null => out.push(ACTION_NULL),
+            Action::Insert { offset, body } => {
+                out.push(ACTION_INSERT);
+                out.extend_from_slice(&encode_u32(body.len(), "Insert body length"));
+                out.extend_from_slice(body.as_bytes());
+                out.extend_from_slice(&encode_u32(*offset, "Insert offset"));
+            }
+            Action::Delete {
+                offset,
+                len,
+                source,
+                source_offset,
+            } => {
+                out.push(ACTION_DELETE);
+                out.extend_from_slice(&encode_u32(*offset, "Delete offset"));
+                out.extend_from_slice(&encode_u32(*len, "Delete len"));
+                match source {
+                    Some(source) => out.extend_from_slice(&source.0),
+                    None => out.extend_from_slice(NULL),
+                }
+                out.extend_from_slice(&encode_u32(
+                    source_offset.unwrap_or(0),
+                    "Delete source_offset",
+                ));
+            }
+        }
+        out
+    }
+
+    /// Inverse of [`Node::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Node, DecodeError> {
+        let mut cursor = bytes;
+
+        let parent_bytes: [u8; 32] = take(&mut cursor, 32)?.try_into().unwrap();
+        let parent = if parent_bytes == *NULL {
+            None
+        } else {
+            Some(NodeId(parent_bytes))
+        };
+
+        let tick = u32::from_be_bytes(take(&mut cursor, 4)?.try_into().unwrap());
+
+        let tag = *take(&mut cursor, 1)?.first().ok_or(DecodeError::UnexpectedEof)?;
+        let action = match tag {
+            ACTION_NULL => Action::Null,
+            ACTION_INSERT => {
+                let len = u32::from_be_bytes(take(&mut cursor, 4)?.try_into().unwrap()) as usize;
+                let body = String::from_utf8(take(&mut cursor, len)?.to_vec())
+                    .map_err(|_| DecodeError::InvalidUtf8)?;
+                let offset = u32::from_be_bytes(take(&mut cursor, 4)?.try_into().unwrap()) as usize;
+                Action::Insert { offset, body }
+            }
+            ACTION_DELETE => {
+                let offset = u32::from_be_bytes(take(&mut cursor, 4)?.try_into().unwrap()) as usize;
+                let len = u32::from_be_bytes(take(&mut cursor, 4)?.try_into().unwrap()) as usize;
+                let source_bytes: [u8; 32] = take(&mut cursor, 32)?.try_into().unwrap();
+                let source = if source_bytes == *NULL {
+                    None
+                } else {
+                    Some(NodeId(source_bytes))
+                };
+                let source_offset_raw =
+                    u32::from_be_bytes(take(&mut cursor, 4)?.try_into().unwrap()) as usize;
+                let source_offset = source.as_ref().map(|_| source_offset_raw);
+                Action::Delete {
+                    offset,
+                    len,
+                    source,
+                    source_offset,
+                }
+            }
+            other => return Err(DecodeError::UnknownAction(other)),
+        };
+
+        Ok(Node {
+            tick,
+            parent,
+            action,
+        })
+    }
+
     pub fn node_id(&self) -> NodeId {
         let mut hasher = Sha512Trunc256::new();
-        if let Some(parent) = self.parent() {
-            hasher.update(parent.0);
-        } else {
-            hasher.update(NULL);
-        }
-        serde_json::to_writer(&mut hasher, &self.tick);
-        serde_json::to_writer(&mut hasher, &self.action);
+        hasher.update(self.encode());
 
         // read hash digest and consume hasher
         let result: [u8; 32] = hasher.finalize().into();
@@ -123,17 +383,17 @@ impl Node {
     pub fn project(&self, buf: &mut MutStr, limit: Option<usize>) -> isize {
         match &self.action {
             Action::Null => {
-                trace!("{}: root", self.node_id().hex4());
+                trace!("{}: root", self.node_id().trace_id());
                 0
             }
             Action::Insert { offset, body } => {
                 trace!(
                     "{}: insert({} of {}, {}) ({})",
-                    self.node_id().hex4(),
+                    self.node_id().trace_id(),
                     offset,
                     buf.len(),
                     body,
-                    self.parent_hex4()
+                    self.parent_trace_id()
                 );
 
                 let slice = match limit {
@@ -145,19 +405,81 @@ impl Node {
                 buf.insert_str(*offset, &slice);
                 slice.len() as isize
             }
-            Action::Delete { offset } => {
+            Action::Delete { offset, len, .. } => {
                 trace!(
-                    "{}: delete({}) ({})",
-                    self.node_id().hex4(),
+                    "{}: delete({}, {}) ({})",
+                    self.node_id().trace_id(),
                     offset,
-                    self.parent_hex4()
+                    len,
+                    self.parent_trace_id()
                 );
-                buf.remove(*offset);
+                buf.remove_chars(*offset, *len);
+
+                -(*len as isize)
+            }
+        }
+    }
+}
 
-                -1
+/// Like replaying [`Node::project`] over `nodes` in causal order, except `Delete`s
+/// carrying a `source` are resolved by unioning their `source_offset` ranges against
+/// that source `Insert` and excluding them when it's rendered, rather than replaying
+/// positionally — so two sibling deletes of overlapping ranges of the same insert
+/// converge regardless of which order they're folded in in `nodes`.
+pub fn project_with_tombstones(nodes: &[&Node]) -> MutStr {
+    let mut tombstones: HashMap<NodeId, Vec<(usize, usize)>> = HashMap::new();
+    for node in nodes {
+        if let Action::Delete {
+            source: Some(source),
+            source_offset: Some(source_offset),
+            len,
+            ..
+        } = &node.action
+        {
+            tombstones
+                .entry(source.clone())
+                .or_insert_with(Vec::new)
+                .push((*source_offset, *source_offset + *len));
+        }
+    }
+    for ranges in tombstones.values_mut() {
+        ranges.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (start, end) in ranges.drain(..) {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
             }
         }
+        *ranges = merged;
     }
+
+    let mut buf = MutStr::new();
+    for node in nodes {
+        match &node.action {
+            Action::Null => {}
+            Action::Insert { offset, body } => match tombstones.get(&node.node_id()) {
+                Some(ranges) => buf.insert_str(*offset, &exclude_char_ranges(body, ranges)),
+                None => buf.insert_str(*offset, body),
+            },
+            Action::Delete { source: None, offset, len, .. } => {
+                buf.remove_chars(*offset, *len);
+            }
+            // Already folded into the source insert's rendered content above.
+            Action::Delete { source: Some(_), .. } => {}
+        }
+    }
+    buf
+}
+
+/// `body` with every char index inside any of `ranges` (each a half-open
+/// `start..end`) removed.
+fn exclude_char_ranges(body: &str, ranges: &[(usize, usize)]) -> String {
+    body.chars()
+        .enumerate()
+        .filter(|(i, _)| !ranges.iter().any(|(start, end)| *i >= *start && *i < *end))
+        .map(|(_, c)| c)
+        .collect()
 }
 
 #[cfg(test)]
@@ -188,7 +510,206 @@ mod test {
         let foo: &[u8; 32] = &(node2.node_id().0);
         assert_eq!(
             hex::encode(foo),
-            "37dbcb6c5f48e99e4530ab2b4b76731abacdca9a3e93dba49690cdbbd69d90b1"
+            "28ac730d51475bf4a28fa2543713e29893500288cea78ade1af0a60159252457"
         )
     }
+
+    #[test]
+    #[should_panic(expected = "exceeds u32::MAX")]
+    fn encode_panics_rather_than_truncating_an_oversized_offset() {
+        let node = Node::new(
+            1,
+            None,
+            Action::Insert {
+                offset: u32::MAX as usize + 1,
+                body: "x".to_string(),
+            },
+        );
+        node.encode();
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let node0 = Node::root(0);
+        let node1 = Node::new(
+            1,
+            Some(node0.node_id()),
+            Action::Insert {
+                offset: 0,
+                body: "Hello".to_string(),
+            },
+        );
+        let node2 = Node::new(
+            2,
+            Some(node1.node_id()),
+            Action::Delete {
+                offset: 3,
+                len: 2,
+                source: Some(node1.node_id()),
+                source_offset: Some(3),
+            },
+        );
+
+        for node in [&node0, &node1, &node2] {
+            let decoded = Node::decode(&node.encode()).unwrap();
+            assert_eq!(decoded.node_id(), node.node_id());
+        }
+    }
+
+    #[test]
+    fn delete_removes_correct_char_range_across_insert_boundaries() {
+        use crate::util::mutstr::MutStr;
+
+        let node0 = Node::root(0);
+        let node1 = Node::new(
+            1,
+            Some(node0.node_id()),
+            Action::Insert {
+                offset: 0,
+                body: "hello".to_string(),
+            },
+        );
+        let node2 = Node::new(
+            2,
+            Some(node1.node_id()),
+            Action::Insert {
+                offset: 5,
+                body: " world".to_string(),
+            },
+        );
+        // Deletes "llo w" — the tail of node1's insert plus the head of node2's.
+        let node3 = Node::new(
+            3,
+            Some(node2.node_id()),
+            Action::Delete {
+                offset: 2,
+                len: 5,
+                source: None,
+                source_offset: None,
+            },
+        );
+
+        let mut buf = MutStr::new();
+        for node in [&node0, &node1, &node2, &node3] {
+            node.project(&mut buf, None);
+        }
+
+        assert_eq!(buf.to_string(), "heorld");
+    }
+
+    #[test]
+    fn delete_operates_on_char_not_byte_boundaries() {
+        use crate::util::mutstr::MutStr;
+
+        let node0 = Node::root(0);
+        let node1 = Node::new(
+            1,
+            Some(node0.node_id()),
+            Action::Insert {
+                offset: 0,
+                body: "héllo".to_string(), // 'é' is multi-byte in UTF-8
+            },
+        );
+        // Deletes the 2nd and 3rd chars ('é', 'l'), not their raw bytes.
+        let node2 = Node::new(
+            2,
+            Some(node1.node_id()),
+            Action::Delete {
+                offset: 1,
+                len: 2,
+                source: Some(node1.node_id()),
+                source_offset: Some(1),
+            },
+        );
+
+        let mut buf = MutStr::new();
+        for node in [&node0, &node1, &node2] {
+            node.project(&mut buf, None);
+        }
+
+        assert_eq!(buf.to_string(), "hlo");
+    }
+
+    #[test]
+    fn to_base_renders_distinct_ids_distinctly() {
+        use super::Radix;
+
+        let a = super::NodeId([0x01; 32]);
+        let b = super::NodeId([0x02; 32]);
+
+        assert_ne!(a.to_base(Radix::Base32), b.to_base(Radix::Base32));
+        assert_ne!(a.to_base(Radix::Base58), b.to_base(Radix::Base58));
+    }
+
+    #[test]
+    fn short_prefix_stays_unique_among_siblings() {
+        use super::Radix;
+
+        let ids = vec![
+            super::NodeId([0x01; 32]),
+            super::NodeId([0x02; 32]),
+            super::NodeId([0x03; 32]),
+        ];
+
+        for id in &ids {
+            let others: Vec<_> = ids.iter().filter(|other| *other != id).cloned().collect();
+            let prefix = id.short_prefix(&others, Radix::Base58);
+            for other in &others {
+                assert!(!other.to_base(Radix::Base58).starts_with(&prefix));
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_sibling_deletes_tombstone_the_same_source_regardless_of_order() {
+        use super::{project_with_tombstones, translate_to_source_offset};
+
+        // The source insert sits at buffer offset 10, not 0 — otherwise a buggy translation that conflates live buffer offset with
+        // source-relative offset would pass by coincidence.
+        let node0 = Node::root(0);
+        let insert = Node::new(
+            1,
+            Some(node0.node_id()),
+            Action::Insert {
+                offset: 10,
+                body: "abcdef".to_string(),
+            },
+        );
+        let insert_id = insert.node_id();
+
+        // Two sibling branches independently delete overlapping ranges of the same
+        // insert: branch_a removes "bcd" (chars 1..4), branch_b removes "cde" (chars
+        // 2..5), each via the same translation `Node::delete` uses (cursor's live
+        // buffer offset -> char index relative to `insert`'s own body), so this
+        // exercises the real coordinate-space fix rather than hand-computed indices.
+        let branch_a = Node::new(
+            2,
+            Some(insert_id.clone()),
+            Action::Delete {
+                offset: 11,
+                len: 3,
+                source: Some(insert_id.clone()),
+                source_offset: Some(translate_to_source_offset(11, &insert)),
+            },
+        );
+        let branch_b = Node::new(
+            2,
+            Some(insert_id.clone()),
+            Action::Delete {
+                offset: 12,
+                len: 3,
+                source: Some(insert_id.clone()),
+                source_offset: Some(translate_to_source_offset(12, &insert)),
+            },
+        );
+
+        // Both tombstone the immutable `source` insert rather than a live buffer
+        // offset, so the union — "bcde" removed, leaving "af" — is the same
+        // regardless of which branch is folded in first.
+        let forward = project_with_tombstones(&[&node0, &insert, &branch_a, &branch_b]);
+        let reversed = project_with_tombstones(&[&node0, &insert, &branch_b, &branch_a]);
+
+        assert_eq!(forward.to_string(), "af");
+        assert_eq!(reversed.to_string(), "af");
+    }
 }
@@ -0,0 +1,313 @@
+//! Content-addressed persistence for [`Node`]s, in the style of openethereum's
+//! `journaldb`: nodes are stored under their own [`NodeId`] hash, so identical
+//! subtrees dedupe automatically instead of being stored twice.
+//!
+//! TODO: `Document` is meant to resolve nodes through a `NodeStore` lazily rather
+//! than holding the whole DAG in memory; that integration isn't wired up yet.
+
+use crate::compress;
+use crate::node::{DecodeError, Node, NodeId};
+use std::collections::{HashMap, HashSet};
+
+/// Stored payloads below this size (the canonical [`Node::encode`] bytes, before
+/// any storage framing) aren't worth the Yaz0 framing overhead.
+const STORAGE_COMPRESSION_THRESHOLD: usize = 256;
+
+const STORAGE_RAW: u8 = 0;
+const STORAGE_COMPRESSED: u8 = 1;
+
+/// Serializes `node` for a [`KvBackend`], compressing the canonical
+/// [`Node::encode`] bytes with Yaz0 when they're large enough to be worth it.
+/// Deliberately wraps rather than touches [`Node::encode`], so `NodeId` stays
+/// independent of the compressor.
+fn encode_for_storage(node: &Node) -> Vec<u8> {
+    let canonical = node.encode();
+    if canonical.len() >= STORAGE_COMPRESSION_THRESHOLD {
+        let mut out = vec![STORAGE_COMPRESSED];
+        out.extend_from_slice(&compress::compress(&canonical));
+        out
+    } else {
+        let mut out = vec![STORAGE_RAW];
+        out.extend_from_slice(&canonical);
+        out
+    }
+}
+
+/// Inverse of [`encode_for_storage`].
+fn decode_from_storage(bytes: &[u8]) -> Result<Node, DecodeError> {
+    let (tag, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEof)?;
+    match *tag {
+        STORAGE_RAW => Node::decode(rest),
+        STORAGE_COMPRESSED => {
+            let canonical = compress::decompress(rest).map_err(|_| DecodeError::Decompress)?;
+            Node::decode(&canonical)
+        }
+        other => Err(DecodeError::BadStorageTag(other)),
+    }
+}
+
+/// A content-addressed store of [`Node`]s keyed by [`NodeId`].
+pub trait NodeStore {
+    /// Look up a previously stored node by id.
+    fn get(&self, id: &NodeId) -> Option<Node>;
+
+    /// Store `node`, returning its id. Storing a node that's already present is a no-op.
+    fn put(&mut self, node: Node) -> NodeId;
+
+    fn contains(&self, id: &NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Mark-and-sweep garbage collection: walk the parent chain backwards from every
+    /// node in `roots` (the live tips), and discard any stored node that isn't reachable
+    /// from one of them. Call this after abandoning a branch to reclaim its nodes.
+    fn prune(&mut self, roots: &[NodeId]);
+
+    /// All ids currently held by the store, in no particular order.
+    fn ids(&self) -> Vec<NodeId>;
+}
+
+/// An in-memory [`NodeStore`] backed by a `HashMap`.
+#[derive(Debug, Default)]
+pub struct MemoryNodeStore {
+    nodes: HashMap<NodeId, Node>,
+}
+
+impl MemoryNodeStore {
+    pub fn new() -> Self {
+        MemoryNodeStore {
+            nodes: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl NodeStore for MemoryNodeStore {
+    fn get(&self, id: &NodeId) -> Option<Node> {
+        self.nodes.get(id).cloned()
+    }
+
+    fn put(&mut self, node: Node) -> NodeId {
+        let id = node.node_id();
+        self.nodes.entry(id.clone()).or_insert(node);
+        id
+    }
+
+    fn prune(&mut self, roots: &[NodeId]) {
+        let mut live: HashSet<NodeId> = HashSet::new();
+        let mut frontier: Vec<NodeId> = roots.to_vec();
+
+        while let Some(id) = frontier.pop() {
+            if !live.insert(id.clone()) {
+                continue;
+            }
+            if let Some(parent) = self.nodes.get(&id).and_then(Node::parent) {
+                frontier.push(parent.clone());
+            }
+        }
+
+        self.nodes.retain(|id, _| live.contains(id));
+    }
+
+    fn ids(&self) -> Vec<NodeId> {
+        self.nodes.keys().cloned().collect()
+    }
+}
+
+/// A pluggable byte-oriented backend a [`NodeStore`] can be layered over (an on-disk KV
+/// store, a remote blob store, etc). Keys are raw [`NodeId`] bytes; values are
+/// [`encode_for_storage`] output: a one-byte framing tag followed by the
+/// [`Node::encode`] bytes, Yaz0-compressed above a size threshold.
+pub trait KvBackend {
+    fn get(&self, key: &[u8; 32]) -> Option<Vec<u8>>;
+    fn put(&mut self, key: [u8; 32], value: Vec<u8>);
+    fn remove(&mut self, key: &[u8; 32]);
+    fn keys(&self) -> Vec<[u8; 32]>;
+}
+
+/// A [`NodeStore`] over any [`KvBackend`], using [`encode_for_storage`]/
+/// [`decode_from_storage`] for the wire format (the canonical [`Node::encode`] bytes,
+/// Yaz0-compressed above a size threshold).
+pub struct KvNodeStore<B: KvBackend> {
+    backend: B,
+}
+
+impl<B: KvBackend> KvNodeStore<B> {
+    pub fn new(backend: B) -> Self {
+        KvNodeStore { backend }
+    }
+}
+
+impl<B: KvBackend> NodeStore for KvNodeStore<B> {
+    fn get(&self, id: &NodeId) -> Option<Node> {
+        let bytes = self.backend.get(&id.0)?;
+        decode_from_storage(&bytes).ok()
+    }
+
+    fn put(&mut self, node: Node) -> NodeId {
+        let id = node.node_id();
+        if self.backend.get(&id.0).is_none() {
+            self.backend.put(id.0, encode_for_storage(&node));
+        }
+        id
+    }
+
+    fn prune(&mut self, roots: &[NodeId]) {
+        let mut live: HashSet<[u8; 32]> = HashSet::new();
+        let mut frontier: Vec<[u8; 32]> = roots.iter().map(|id| id.0).collect();
+
+        while let Some(key) = frontier.pop() {
+            if !live.insert(key) {
+                continue;
+            }
+            if let Some(bytes) = self.backend.get(&key) {
+                if let Ok(node) = decode_from_storage(&bytes) {
+                    if let Some(parent) = node.parent() {
+                        frontier.push(parent.0);
+                    }
+                }
+            }
+        }
+
+        for key in self.backend.keys() {
+            if !live.contains(&key) {
+                self.backend.remove(&key);
+            }
+        }
+    }
+
+    fn ids(&self) -> Vec<NodeId> {
+        self.backend.keys().into_iter().map(NodeId).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::node::Action;
+
+    #[test]
+    fn dedupes_identical_subtrees() {
+        let mut store = MemoryNodeStore::new();
+        let root = Node::root(0);
+        let root_id = store.put(root.clone());
+
+        let a = Node::new(
+            1,
+            Some(root_id.clone()),
+            Action::Insert {
+                offset: 0,
+                body: "x".to_string(),
+            },
+        );
+        let b = Node::new(
+            1,
+            Some(root_id.clone()),
+            Action::Insert {
+                offset: 0,
+                body: "x".to_string(),
+            },
+        );
+
+        let a_id = store.put(a);
+        let b_id = store.put(b);
+        assert_eq!(a_id, b_id);
+        assert_eq!(store.len(), 2);
+    }
+
+    #[derive(Default)]
+    struct TestKvBackend {
+        entries: HashMap<[u8; 32], Vec<u8>>,
+    }
+
+    impl KvBackend for TestKvBackend {
+        fn get(&self, key: &[u8; 32]) -> Option<Vec<u8>> {
+            self.entries.get(key).cloned()
+        }
+
+        fn put(&mut self, key: [u8; 32], value: Vec<u8>) {
+            self.entries.insert(key, value);
+        }
+
+        fn remove(&mut self, key: &[u8; 32]) {
+            self.entries.remove(key);
+        }
+
+        fn keys(&self) -> Vec<[u8; 32]> {
+            self.entries.keys().cloned().collect()
+        }
+    }
+
+    #[test]
+    fn large_insert_bodies_are_compressed_in_storage_but_not_in_the_hash() {
+        let mut store = KvNodeStore::new(TestKvBackend::default());
+        let root_id = store.put(Node::root(0));
+
+        let body = "abababababab".repeat(100);
+        assert!(body.len() >= super::STORAGE_COMPRESSION_THRESHOLD);
+        let node = Node::new(
+            1,
+            Some(root_id),
+            Action::Insert {
+                offset: 0,
+                body: body.clone(),
+            },
+        );
+
+        // The NodeId is the hash of the uncompressed canonical encoding, so it must
+        // not depend on how (or whether) the storage layer compresses the body.
+        let uncompressed_node_id = node.node_id();
+
+        let id = store.put(node);
+        assert_eq!(id, uncompressed_node_id);
+
+        let stored_bytes = store.backend.get(&id.0).unwrap();
+        assert!(stored_bytes.len() < body.len());
+
+        let roundtripped = store.get(&id).unwrap();
+        match roundtripped.action {
+            Action::Insert { body: stored_body, .. } => assert_eq!(stored_body, body),
+            _ => panic!("expected Insert"),
+        }
+    }
+
+    #[test]
+    fn prune_sweeps_abandoned_branch() {
+        let mut store = MemoryNodeStore::new();
+        let root = Node::root(0);
+        let root_id = store.put(root);
+
+        let live_tip = Node::new(
+            1,
+            Some(root_id.clone()),
+            Action::Insert {
+                offset: 0,
+                body: "kept".to_string(),
+            },
+        );
+        let live_id = store.put(live_tip);
+
+        let abandoned = Node::new(
+            1,
+            Some(root_id.clone()),
+            Action::Insert {
+                offset: 0,
+                body: "discarded".to_string(),
+            },
+        );
+        store.put(abandoned);
+
+        assert_eq!(store.len(), 3);
+        store.prune(&[live_id.clone()]);
+        assert_eq!(store.len(), 2);
+        assert!(store.contains(&live_id));
+        assert!(store.contains(&root_id));
+    }
+}